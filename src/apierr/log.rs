@@ -0,0 +1,111 @@
+//! Logging hook for discarded errors.
+
+use std::fmt::Debug;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use tracing::Level;
+
+use super::{catch, cvcode, default_response};
+
+/// Configures how [`stop_logged`]/[`catch_logged`] log a discarded error.
+///
+/// Defaults to logging at [`Level::ERROR`], matching how axum's
+/// `ErrorResponse::into_response` logs `tracing::error!(error = %error)`.
+/// Use [`LogPolicy::level`] to send 4xx errors to `warn`/`debug` and keep
+/// 5xx at `error`.
+///
+/// NOTE: `tracing::event!`'s `target:` argument has to be a string literal
+/// baked into the callsite at macro-expansion time, so it can't be made
+/// configurable through a builder field the way the level can. Events
+/// logged through this type always use this module's path (`apierr::log`)
+/// as their target, not the path of the module that calls
+/// [`stop_logged`]/[`catch_logged`].
+pub struct LogPolicy {
+    level: Level,
+}
+
+impl Default for LogPolicy {
+    fn default() -> Self {
+        Self {
+            level: Level::ERROR,
+        }
+    }
+}
+
+impl LogPolicy {
+    /// Create a [`LogPolicy`] with the default level ([`Level::ERROR`]).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the level to log at.
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    fn log<E: Debug>(&self, code: StatusCode, error: &E) {
+        match self.level {
+            Level::ERROR => tracing::event!(Level::ERROR, status = code.as_u16(), error = ?error),
+            Level::WARN => tracing::event!(Level::WARN, status = code.as_u16(), error = ?error),
+            Level::INFO => tracing::event!(Level::INFO, status = code.as_u16(), error = ?error),
+            Level::DEBUG => tracing::event!(Level::DEBUG, status = code.as_u16(), error = ?error),
+            Level::TRACE => tracing::event!(Level::TRACE, status = code.as_u16(), error = ?error),
+        }
+    }
+}
+
+/// A variant of [`catch`](super::catch) that emits a `tracing` event
+/// carrying the error's `Debug` form and the chosen status code, before
+/// falling back to `default` when `handle` returns `None`.
+///
+/// HINT: Use [`LogPolicy`] to pick the level (`warn`/`debug` for 4xx,
+/// `error` for 5xx).
+pub fn catch_logged<C, D, E, F, R, Z>(
+    code: C,
+    handle: F,
+    default: D,
+    policy: LogPolicy,
+) -> impl FnOnce(E) -> Response
+where
+    C: TryInto<StatusCode, Error = Z>,
+    D: Fn(StatusCode) -> Response,
+    F: FnOnce(StatusCode, E) -> Option<R>,
+    R: IntoResponse,
+    E: Debug,
+    Z: Debug,
+{
+    move |e| {
+        let code = cvcode(code);
+        policy.log(code, &e);
+        catch(code, handle, default)(e)
+    }
+}
+
+/// A variant of [`stop`](super::stop) that logs the discarded error via
+/// [`catch_logged`] before producing the hidden default response.
+pub fn stop_logged<C, E, Z>(code: C, policy: LogPolicy) -> impl FnOnce(E) -> Response
+where
+    C: TryInto<StatusCode, Error = Z>,
+    E: Debug,
+    Z: Debug,
+{
+    catch_logged(code, |_, _| None::<Response>, default_response, policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stop_logged_still_hides_the_error_behind_the_default_response() {
+        let response = stop_logged(StatusCode::INTERNAL_SERVER_ERROR, LogPolicy::new().level(Level::WARN))("boom");
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(response
+            .headers()
+            .contains_key(crate::apierr::DEFAULT_RESPONSE_MARKER));
+    }
+}
@@ -0,0 +1,306 @@
+//! Api Error Handling
+//!
+//! You would be particularly interested in these functions
+//! (and I recommend looking at them in this order):
+//! - [`stop`]: Map error, hide it from the user, set status code.
+//! - [`transparent_stop`]: Map error, show the user, set status code.
+//! - [`catch`]: Map error, do custom handling, set status code.
+//! - [`ResponseError`]/[`report`]: Let an error type carry its own status code.
+//! - [`problem`]/[`problem_response`]: RFC 7807 `application/problem+json` bodies.
+//! - [`negotiate::NegotiateErrors`]: Content-negotiating error middleware.
+//! - [`named`]: Named status-code constructors like `bad_request`/`not_found`.
+//! - [`stop_logged`]/[`catch_logged`]: Log a discarded error before hiding it.
+//! - [`ApiResult`]: A `Result`-like type implementing `IntoResponse` (and,
+//!   on nightly with the `nightly` feature, `Try`).
+//!
+//! See also: [`Result::map_err`].
+
+mod log;
+mod named;
+mod negotiate;
+mod result;
+
+pub use log::{catch_logged, stop_logged, LogPolicy};
+pub use named::*;
+pub use negotiate::NegotiateErrors;
+pub use result::ApiResult;
+
+use std::fmt::{Debug, Display};
+
+use axum::{
+    http::{header, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+/// Marker header set by [`default_response`] on its own output.
+///
+/// [`negotiate::NegotiateErrors`] looks for this (and strips it before the
+/// response is sent) to tell an unstyled default body apart from a custom
+/// `catch` handler's body that also happens to be `text/plain` — e.g. one
+/// produced by [`transparent`]. Content-Type sniffing can't make that
+/// distinction, since both are `text/plain`.
+pub(crate) const DEFAULT_RESPONSE_MARKER: &str = "x-apierr-default-response";
+
+/// API Result
+pub type Result<R = Response, E = Response> = std::result::Result<R, E>;
+
+/// Return a closure that takes what would be an error type
+/// and then discards it, while giving a default error message to the user.
+///
+/// (A plain text message with the canonical reason, if exists.)
+///
+/// This default responder is used: [`default_response`].
+pub fn stop<C, E, Z>(code: C) -> impl FnOnce(E) -> Response
+where
+    C: TryInto<StatusCode, Error = Z>,
+    Z: Debug,
+{
+    catch(code, |_, _| None::<Response>, default_response)
+}
+
+/// A variety of [`stop`] that, rather than hiding the error from
+/// the user, shows it using a call to [`transparent`].
+pub fn transparent_stop<C, E, Z>(code: C) -> impl FnOnce(E) -> Response
+where
+    C: TryInto<StatusCode, Error = Z>,
+    E: Display,
+    Z: Debug,
+{
+    catch(code, transparent, default_response)
+}
+
+/// Return a closure that takes what would be an error type
+/// and then consumes it, producing an optional custom response.
+/// If the response is generated, it is served to the user.
+/// If the response is not generated, a default error message is given to the user.
+///
+/// NOTE: when the custom response is generated, it's the responsibility of the
+/// caller to set the status code. The `code` provided is only the default
+/// status code to use when the custom response is NOT generated.
+///
+/// HINT: For the default response generator (`default`), you can use
+/// [`default_response`] without any problem.
+///
+/// NOTE: There are no restrictions on the type of the error.
+pub fn catch<C, D, E, F, R, Z>(code: C, handle: F, default: D) -> impl FnOnce(E) -> Response
+where
+    C: TryInto<StatusCode, Error = Z>,
+    D: Fn(StatusCode) -> Response,
+    F: FnOnce(StatusCode, E) -> Option<R>,
+    R: IntoResponse,
+    Z: Debug,
+{
+    move |e| {
+        let code = cvcode(code);
+        if let Some(r) = handle(code, e) {
+            r.into_response()
+        } else {
+            default(code)
+        }
+    }
+}
+
+/// Create the default error response for a given status code.
+///
+/// HINT: Use as the third argument for the [`catch`] function.
+pub fn default_response(code: StatusCode) -> Response
+where
+{
+    let code = cvcode(code);
+    let body = format!("{}", code); // It gives the numeric code + reason.
+    let mut response = (code, body).into_response();
+    response.headers_mut().insert(
+        HeaderName::from_static(DEFAULT_RESPONSE_MARKER),
+        HeaderValue::from_static("1"),
+    );
+    response
+}
+
+/// Create a response by calling Display implementation on an
+/// error type and returning a plain text message.
+///
+/// HINT: Use as the second argument for the [`catch`] function.
+pub fn transparent<D>(code: StatusCode, error: D) -> Option<Response>
+where
+    D: Display,
+{
+    let body = format!("{}", error);
+    Some((code, body).into_response())
+}
+
+/// A trait for error types that know how to render themselves as a response.
+///
+/// Borrowed from Poem's `ResponseError` design: implement this once on a
+/// domain error enum, and use [`report`] (or [`report_err`] inside
+/// `map_err`) instead of threading a [`StatusCode`] through every call
+/// site that produces this error.
+pub trait ResponseError {
+    /// The status code to report for this error.
+    fn status(&self) -> StatusCode;
+
+    /// An optional custom response body.
+    ///
+    /// Return `None` (the default) to fall back to [`default_response`].
+    fn body(&self) -> Option<Response> {
+        None
+    }
+}
+
+/// Render an error that implements [`ResponseError`] into a [`Response`],
+/// using [`ResponseError::status`] and falling back to [`default_response`]
+/// when [`ResponseError::body`] returns `None`.
+pub fn report<E: ResponseError>(e: E) -> Response {
+    let code = e.status();
+    e.body().unwrap_or_else(|| default_response(code))
+}
+
+/// A variety of [`report`] for use as a `map_err` closure, mirroring
+/// [`stop`]/[`transparent_stop`] which also return closures rather than
+/// being called directly.
+pub fn report_err<E: ResponseError>() -> impl FnOnce(E) -> Response {
+    report
+}
+
+/// An RFC 7807 "Problem Details for HTTP APIs" object.
+///
+/// HINT: Use [`problem`]/[`problem_response`] as the `handle`/`default`
+/// arguments for [`catch`] rather than constructing this directly, unless
+/// you need to attach extension members (see [`Problem::with_extensions`]).
+#[derive(Serialize)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Problem {
+    /// Build a Problem Details object with `type` set to `"about:blank"`
+    /// and `title` set to the status code's canonical reason.
+    pub fn new(code: StatusCode, detail: impl Into<String>) -> Self {
+        Self {
+            type_: "about:blank".to_string(),
+            title: code.canonical_reason().unwrap_or_default().to_string(),
+            status: code.as_u16(),
+            detail: detail.into(),
+            extensions: serde_json::Map::new(),
+        }
+    }
+
+    /// Attach extension members (e.g. `instance`, a correlation id) to the
+    /// Problem Details object.
+    pub fn with_extensions(mut self, extensions: serde_json::Map<String, serde_json::Value>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+}
+
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        let code = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = (code, Json(self)).into_response();
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/problem+json"),
+        );
+        response
+    }
+}
+
+/// Create a response by serializing an error's [`Display`] output as an
+/// RFC 7807 `application/problem+json` body.
+///
+/// HINT: Use as the second argument for the [`catch`] function.
+pub fn problem<D>(code: StatusCode, error: D) -> Option<Response>
+where
+    D: Display,
+{
+    Some(Problem::new(code, error.to_string()).into_response())
+}
+
+/// Like [`problem`], but attaches the given extension members to every
+/// Problem Details object it produces.
+///
+/// HINT: Use as the second argument for the [`catch`] function.
+pub fn problem_with<D>(
+    extensions: serde_json::Map<String, serde_json::Value>,
+) -> impl Fn(StatusCode, D) -> Option<Response>
+where
+    D: Display,
+{
+    move |code, error| {
+        Some(
+            Problem::new(code, error.to_string())
+                .with_extensions(extensions.clone())
+                .into_response(),
+        )
+    }
+}
+
+/// Create the default RFC 7807 `application/problem+json` response for a
+/// given status code, using the canonical reason as the `detail`.
+///
+/// HINT: Use as the third argument for the [`catch`] function.
+pub fn problem_response(code: StatusCode) -> Response {
+    let detail = code.canonical_reason().unwrap_or_default().to_string();
+    Problem::new(code, detail).into_response()
+}
+
+/// Convert what could be a status code into a [`StatusCode`], or
+/// panic if the conversion fails.
+fn cvcode<C, Z>(code: C) -> StatusCode
+where
+    C: TryInto<StatusCode, Error = Z>,
+    Z: Debug,
+{
+    code.try_into().expect("invalid status code")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MyError;
+
+    impl ResponseError for MyError {
+        fn status(&self) -> StatusCode {
+            StatusCode::IM_A_TEAPOT
+        }
+    }
+
+    #[test]
+    fn report_uses_the_error_s_own_status_and_falls_back_to_default_response() {
+        let response = report(MyError);
+        assert_eq!(response.status(), StatusCode::IM_A_TEAPOT);
+        assert!(response.headers().contains_key(DEFAULT_RESPONSE_MARKER));
+    }
+
+    #[test]
+    fn problem_serializes_rfc7807_fields_and_extensions() {
+        let mut extensions = serde_json::Map::new();
+        extensions.insert("instance".to_string(), serde_json::json!("/orders/42"));
+        let problem = Problem::new(StatusCode::NOT_FOUND, "no such order").with_extensions(extensions);
+
+        let value = serde_json::to_value(&problem).unwrap();
+        assert_eq!(value["type"], "about:blank");
+        assert_eq!(value["title"], "Not Found");
+        assert_eq!(value["status"], 404);
+        assert_eq!(value["detail"], "no such order");
+        assert_eq!(value["instance"], "/orders/42");
+    }
+
+    #[test]
+    fn problem_into_response_sets_the_problem_json_content_type() {
+        let response = Problem::new(StatusCode::NOT_FOUND, "missing").into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/problem+json"
+        );
+    }
+}
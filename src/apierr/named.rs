@@ -0,0 +1,83 @@
+//! Named status-code constructors.
+
+use std::fmt::Display;
+
+use axum::{http::StatusCode, response::Response};
+
+use super::{default_response, transparent};
+
+/// Define a named status-code constructor plus a `_stop` variant suitable
+/// for `map_err`, following Poem's `define_http_error!` approach.
+///
+/// Each entry pairs a fixed [`StatusCode`] with a handling policy:
+/// `transparent` shows the error to the user (via [`transparent`]), while
+/// `hidden` discards it in favor of [`default_response`] (like [`stop`]).
+///
+/// This removes the repeated `stop(500)` / `transparent_stop(StatusCode::BAD_REQUEST)`
+/// magic numbers at call sites in favor of self-documenting names.
+macro_rules! define_http_error {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident, $stop_name:ident => $code:expr, $policy:ident
+    );* $(;)?) => {
+        $(
+            $(#[$meta])*
+            pub fn $name<E: Display>(e: E) -> Response {
+                define_http_error!(@apply $policy, $code, e)
+            }
+
+            $(#[$meta])*
+            pub fn $stop_name<E: Display>() -> impl FnOnce(E) -> Response {
+                $name
+            }
+        )*
+    };
+    (@apply transparent, $code:expr, $e:expr) => {
+        transparent($code, $e).unwrap()
+    };
+    (@apply hidden, $code:expr, $e:expr) => {{
+        let _ = $e;
+        default_response($code)
+    }};
+}
+
+define_http_error! {
+    /// 400 Bad Request. The error is shown to the user.
+    bad_request, bad_request_stop => StatusCode::BAD_REQUEST, transparent;
+    /// 401 Unauthorized. The error is shown to the user.
+    unauthorized, unauthorized_stop => StatusCode::UNAUTHORIZED, transparent;
+    /// 403 Forbidden. The error is shown to the user.
+    forbidden, forbidden_stop => StatusCode::FORBIDDEN, transparent;
+    /// 404 Not Found. The error is shown to the user.
+    not_found, not_found_stop => StatusCode::NOT_FOUND, transparent;
+    /// 409 Conflict. The error is shown to the user.
+    conflict, conflict_stop => StatusCode::CONFLICT, transparent;
+    /// 422 Unprocessable Entity. The error is shown to the user.
+    unprocessable_entity, unprocessable_entity_stop => StatusCode::UNPROCESSABLE_ENTITY, transparent;
+    /// 500 Internal Server Error. The error is hidden from the user.
+    internal_server_error, internal_server_error_stop => StatusCode::INTERNAL_SERVER_ERROR, hidden;
+    /// 503 Service Unavailable. The error is hidden from the user.
+    service_unavailable, service_unavailable_stop => StatusCode::SERVICE_UNAVAILABLE, hidden;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn bad_request_shows_the_error_to_the_user() {
+        let response = bad_request("missing field");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&bytes[..], b"missing field");
+    }
+
+    #[test]
+    fn internal_server_error_hides_the_error_from_the_user() {
+        let response = internal_server_error("db connection refused");
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(response
+            .headers()
+            .contains_key(crate::apierr::DEFAULT_RESPONSE_MARKER));
+    }
+}
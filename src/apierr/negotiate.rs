@@ -0,0 +1,188 @@
+//! Content negotiation for error responses.
+
+use std::task::{Context, Poll};
+
+use axum::{
+    body::Body,
+    http::{header, HeaderName, HeaderValue, Request, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+use serde::Serialize;
+use tower::{Layer, Service};
+
+use super::{problem_response, DEFAULT_RESPONSE_MARKER};
+
+/// A [`tower::Layer`] that re-renders error responses (status >= 400) to
+/// match the incoming request's `Accept` header.
+///
+/// Only the default plain text body produced by
+/// [`default_response`](super::default_response) is ever rewritten, and
+/// only when the client actually prefers something else (`application/json`
+/// or `application/problem+json`). A body produced by a custom `catch`
+/// handler is always left untouched, even one that also happens to be
+/// `text/plain` (e.g. one produced by [`transparent`](super::transparent)) —
+/// `default_response` tags its own output with a marker header rather than
+/// relying on `Content-Type` sniffing, which can't tell the two apart.
+///
+/// This mirrors how Rocket's default catcher and `gotham_restful` pick a
+/// representation per request.
+#[derive(Clone, Copy, Default)]
+pub struct NegotiateErrors;
+
+impl NegotiateErrors {
+    /// Create a new [`NegotiateErrors`] layer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for NegotiateErrors {
+    type Service = NegotiateErrorsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        NegotiateErrorsService { inner }
+    }
+}
+
+/// The [`Service`] produced by [`NegotiateErrors`].
+#[derive(Clone)]
+pub struct NegotiateErrorsService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for NegotiateErrorsService<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let accept = req.headers().get(header::ACCEPT).cloned();
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move {
+            let response = inner.call(req).await?;
+            Ok(negotiate(response, accept))
+        })
+    }
+}
+
+fn negotiate(mut response: Response, accept: Option<HeaderValue>) -> Response {
+    let status = response.status();
+    if !status.is_client_error() && !status.is_server_error() {
+        return response;
+    }
+
+    // Only a response carrying `default_response`'s marker is ours to
+    // rewrite; a custom `catch` handler already made its own choice, and
+    // the marker must never reach the client either way.
+    let is_default = response
+        .headers_mut()
+        .remove(HeaderName::from_static(DEFAULT_RESPONSE_MARKER))
+        .is_some();
+    if !is_default {
+        return response;
+    }
+
+    match preferred_media_type(accept.as_ref()) {
+        MediaType::PlainText => response,
+        MediaType::Json => json_error(status),
+        MediaType::Problem => problem_response(status),
+    }
+}
+
+enum MediaType {
+    PlainText,
+    Json,
+    Problem,
+}
+
+/// Pick a media type from the `Accept` header, preferring the first
+/// candidate we support and falling back to plain text.
+fn preferred_media_type(accept: Option<&HeaderValue>) -> MediaType {
+    let Some(accept) = accept.and_then(|v| v.to_str().ok()) else {
+        return MediaType::PlainText;
+    };
+    for candidate in accept.split(',') {
+        let candidate = candidate.split(';').next().unwrap_or("").trim();
+        match candidate {
+            "application/problem+json" => return MediaType::Problem,
+            "application/json" => return MediaType::Json,
+            "text/plain" | "*/*" => return MediaType::PlainText,
+            _ => continue,
+        }
+    }
+    MediaType::PlainText
+}
+
+#[derive(Serialize)]
+struct JsonError {
+    status: u16,
+    error: String,
+}
+
+fn json_error(code: StatusCode) -> Response {
+    let body = JsonError {
+        status: code.as_u16(),
+        error: code.canonical_reason().unwrap_or_default().to_string(),
+    };
+    (code, Json(body)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::body::to_bytes;
+    use tower::ServiceExt;
+
+    use super::super::{default_response, transparent};
+    use super::*;
+
+    #[tokio::test]
+    async fn rewrites_the_default_body_to_match_accept() {
+        let svc = tower::service_fn(|_req: Request<Body>| async {
+            Ok::<_, std::convert::Infallible>(default_response(StatusCode::NOT_FOUND))
+        });
+        let svc = NegotiateErrors::new().layer(svc);
+
+        let request = Request::builder()
+            .header(header::ACCEPT, "application/json")
+            .body(Body::empty())
+            .unwrap();
+        let response = svc.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let bytes = to_bytes(response.into_body()).await.unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(value["status"], 404);
+    }
+
+    /// Regression test: a `transparent` body is also `text/plain`, but it is
+    /// NOT `default_response`'s output and must never be rewritten.
+    #[tokio::test]
+    async fn leaves_a_transparent_catch_body_untouched() {
+        let svc = tower::service_fn(|_req: Request<Body>| async {
+            Ok::<_, std::convert::Infallible>(
+                transparent(StatusCode::BAD_REQUEST, "too long").unwrap(),
+            )
+        });
+        let svc = NegotiateErrors::new().layer(svc);
+
+        let request = Request::builder()
+            .header(header::ACCEPT, "application/json")
+            .body(Body::empty())
+            .unwrap();
+        let response = svc.oneshot(request).await.unwrap();
+
+        let bytes = to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&bytes[..], b"too long");
+    }
+}
@@ -0,0 +1,163 @@
+//! A first-class result type that implements [`IntoResponse`], and on
+//! nightly, `Try`.
+
+use std::convert::Infallible;
+use std::fmt::Display;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+
+use super::{default_response, transparent, ResponseError};
+
+struct ApiError<E> {
+    status: StatusCode,
+    error: E,
+    show: bool,
+}
+
+/// A result type that implements [`IntoResponse`], so a handler can return
+/// it directly instead of calling `.map_err(stop(..))` at every boundary.
+///
+/// With the `nightly` feature enabled, `ApiResult` also implements `Try`,
+/// so `?` propagates errors while attaching a status code and a rendering
+/// policy (hidden behind [`default_response`], or shown via [`transparent`]),
+/// instead of the default "everything becomes 500" behavior — following the
+/// motivation behind the `resp-result` crate.
+pub struct ApiResult<T, E = Infallible> {
+    inner: std::result::Result<T, ApiError<E>>,
+}
+
+impl<T, E> ApiResult<T, E> {
+    /// A successful result.
+    pub fn ok(value: T) -> Self {
+        Self { inner: Ok(value) }
+    }
+
+    /// A failed result whose error is hidden from the user behind
+    /// [`default_response`].
+    pub fn hidden(status: StatusCode, error: E) -> Self {
+        Self {
+            inner: Err(ApiError {
+                status,
+                error,
+                show: false,
+            }),
+        }
+    }
+
+    /// A failed result whose error is shown to the user via [`transparent`].
+    pub fn transparent(status: StatusCode, error: E) -> Self {
+        Self {
+            inner: Err(ApiError {
+                status,
+                error,
+                show: true,
+            }),
+        }
+    }
+}
+
+impl<T, E> IntoResponse for ApiResult<T, E>
+where
+    T: IntoResponse,
+    E: Display,
+{
+    fn into_response(self) -> Response {
+        match self.inner {
+            Ok(value) => value.into_response(),
+            Err(ApiError {
+                status,
+                error,
+                show: true,
+            }) => transparent(status, error).unwrap(),
+            Err(ApiError { status, .. }) => default_response(status),
+        }
+    }
+}
+
+/// Propagate a plain [`Result`](std::result::Result) through `?` into an
+/// [`ApiResult`], using [`ResponseError::status`] for the status code and
+/// hiding the error behind [`default_response`].
+///
+/// HINT: Use [`ApiResult::transparent`] directly when the error should be
+/// shown to the user instead.
+#[cfg(feature = "nightly")]
+impl<T, E> std::ops::FromResidual<std::result::Result<Infallible, E>> for ApiResult<T, E>
+where
+    E: ResponseError,
+{
+    fn from_residual(residual: std::result::Result<Infallible, E>) -> Self {
+        let Err(error) = residual;
+        let status = error.status();
+        Self::hidden(status, error)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<T, E> std::ops::FromResidual for ApiResult<T, E> {
+    fn from_residual(residual: ApiResult<Infallible, E>) -> Self {
+        match residual.inner {
+            Err(e) => Self { inner: Err(e) },
+            Ok(infallible) => match infallible {},
+        }
+    }
+}
+
+/// Lets `ApiResult<Infallible, E>` stand in as the `Residual` for any
+/// `ApiResult<T, E>`, so generic code bounded on
+/// [`Residual`](std::ops::Residual) can name the matching `ApiResult` type.
+#[cfg(feature = "nightly")]
+impl<T, E> std::ops::Residual<T> for ApiResult<Infallible, E> {
+    type TryType = ApiResult<T, E>;
+}
+
+#[cfg(feature = "nightly")]
+impl<T, E> std::ops::Try for ApiResult<T, E> {
+    type Output = T;
+    type Residual = ApiResult<Infallible, E>;
+
+    fn from_output(output: T) -> Self {
+        Self::ok(output)
+    }
+
+    fn branch(self) -> std::ops::ControlFlow<Self::Residual, T> {
+        match self.inner {
+            Ok(value) => std::ops::ControlFlow::Continue(value),
+            Err(e) => std::ops::ControlFlow::Break(ApiResult { inner: Err(e) }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ok_is_returned_as_is() {
+        let response = ApiResult::<&'static str, &'static str>::ok("good").into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn hidden_error_falls_back_to_the_default_response() {
+        let response =
+            ApiResult::<&'static str, &'static str>::hidden(StatusCode::INTERNAL_SERVER_ERROR, "boom")
+                .into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(response
+            .headers()
+            .contains_key(crate::apierr::DEFAULT_RESPONSE_MARKER));
+    }
+
+    #[tokio::test]
+    async fn transparent_error_is_shown_to_the_user() {
+        let response =
+            ApiResult::<&'static str, &'static str>::transparent(StatusCode::BAD_REQUEST, "too long")
+                .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&bytes[..], b"too long");
+    }
+}
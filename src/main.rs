@@ -1,12 +1,55 @@
+// `ApiResult`'s `Try`/`Residual` impls (see `apierr::result`) are nightly-only.
+#![cfg_attr(feature = "nightly", feature(try_trait_v2, try_trait_v2_residual))]
+
 mod apierr;
 
 use axum::{
-    extract::Path, http::StatusCode, response::IntoResponse, routing::get, Json, Router, Server,
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router, Server,
 };
 use serde::Serialize;
 
 use crate::apierr::*;
 
+/// A domain error that carries its own status code via [`ResponseError`],
+/// instead of a call site having to pick one.
+enum RateLimitError {
+    TooManyRequests,
+}
+
+impl ResponseError for RateLimitError {
+    fn status(&self) -> StatusCode {
+        StatusCode::TOO_MANY_REQUESTS
+    }
+}
+
+async fn rate_limited() -> Response {
+    // `report` reads the status straight off the error.
+    report(RateLimitError::TooManyRequests)
+}
+
+fn widget(id: &str) -> std::result::Result<&'static str, String> {
+    Err(format!("no widget named {id:?}"))
+}
+
+async fn widget_lookup(Path(id): Path<String>) -> Result<impl IntoResponse> {
+    // `problem`/`problem_response` render an RFC 7807 `application/problem+json`
+    // body instead of `transparent`/`default_response`'s plain text.
+    widget(&id).map_err(catch(StatusCode::NOT_FOUND, problem, problem_response))
+}
+
+async fn widget_lookup_api_result(Path(id): Path<String>) -> ApiResult<&'static str, String> {
+    // `ApiResult` implements `IntoResponse` directly, so a handler can
+    // return it instead of mapping the error by hand.
+    match widget(&id) {
+        Ok(name) => ApiResult::ok(name),
+        Err(error) => ApiResult::transparent(StatusCode::NOT_FOUND, error),
+    }
+}
+
 fn bad() -> Result<&'static str, &'static str> {
     Err("bad")
 }
@@ -19,12 +62,18 @@ async fn always_500() -> Result<&'static str> {
     // The user sees: "500 Internal Server Error"
     // The user does NOT see: "bad"
     // So, by default, we hide the error from the user.
-    bad().map_err(stop(500))
+    bad().map_err(internal_server_error_stop())
+}
+
+async fn always_500_logged() -> Result<&'static str> {
+    // Same as `always_500`, except the discarded error is first emitted as
+    // a `tracing::error!` event, so it isn't lost to operators too.
+    bad().map_err(stop_logged(StatusCode::INTERNAL_SERVER_ERROR, LogPolicy::new()))
 }
 
 async fn always_200() -> Result<&'static str> {
     // The user sees "good" and the status code is 200.
-    good().map_err(stop(500))
+    good().map_err(internal_server_error_stop())
 }
 
 async fn error_with_custom_feedback(number: Option<Path<String>>) -> Result<impl IntoResponse> {
@@ -84,16 +133,23 @@ async fn error_with_custom_feedback(number: Option<Path<String>>) -> Result<impl
         .map_err(catch(400, not_a_number, default_response))?;
 
     // Ok, now, let's check the ranges.
-    // (NOTE: transparent_stop is an alias for catch(_, transparent, default_response)).
-    validate_range(number).map_err(transparent_stop(StatusCode::BAD_REQUEST))
+    // (NOTE: bad_request_stop is transparent_stop(StatusCode::BAD_REQUEST) under a
+    // self-documenting name — see apierr::named).
+    validate_range(number).map_err(bad_request_stop())
 }
 
 fn router() -> Router {
     Router::new()
         .route("/", get(always_200))
         .route("/500", get(always_500))
+        .route("/500/logged", get(always_500_logged))
         .route("/custom", get(error_with_custom_feedback))
         .route("/custom/:number", get(error_with_custom_feedback))
+        .route("/rate-limited", get(rate_limited))
+        .route("/widgets/:id", get(widget_lookup))
+        .route("/widgets/:id/api-result", get(widget_lookup_api_result))
+        // Re-render error responses to match the request's `Accept` header.
+        .layer(NegotiateErrors::new())
 }
 
 #[tokio::main]